@@ -0,0 +1,52 @@
+//! Expose the resolved versions of the dependencies reported by
+//! `runjs.version`, which cargo does not pass to the crate itself.
+
+use std::path::PathBuf;
+
+/// Dependencies whose versions are exported, with the variable each is set in
+const REPORTED: &[(&str, &str)] = &[
+    ("deno_core", "RUNJS_DENO_CORE_VERSION"),
+    ("deno_ast", "RUNJS_DENO_AST_VERSION"),
+];
+
+fn main() {
+    let lock = find_lockfile();
+    let contents = lock
+        .as_ref()
+        .and_then(|lock| std::fs::read_to_string(lock).ok())
+        .unwrap_or_default();
+    for (name, var) in REPORTED {
+        let version = locked_version(&contents, name).unwrap_or("unknown");
+        println!("cargo:rustc-env={}={}", var, version);
+    }
+    if let Some(lock) = lock {
+        println!("cargo:rerun-if-changed={}", lock.display());
+    }
+    println!("cargo:rerun-if-changed=build.rs");
+}
+
+/// The Cargo.lock of the package, or of the workspace building it as a
+/// dependency, whose target directory holds `OUT_DIR`
+fn find_lockfile() -> Option<PathBuf> {
+    let manifest_dir = PathBuf::from(std::env::var_os("CARGO_MANIFEST_DIR")?);
+    let out_dir = PathBuf::from(std::env::var_os("OUT_DIR")?);
+    manifest_dir
+        .ancestors()
+        .chain(out_dir.ancestors())
+        .map(|dir| dir.join("Cargo.lock"))
+        .find(|lock| lock.is_file())
+}
+
+/// The version of the package `name` in the lockfile `contents`
+fn locked_version<'a>(contents: &'a str, name: &str) -> Option<&'a str> {
+    let name_line = format!("name = \"{}\"", name);
+    contents.split("[[package]]").find_map(|package| {
+        let mut lines = package.lines().map(str::trim);
+        if !lines.clone().any(|line| line == name_line) {
+            return None;
+        }
+        lines
+            .find_map(|line| line.strip_prefix("version = \""))
+            .and_then(|version| version.strip_suffix('"'))
+    })
+}
@@ -1,5 +1,5 @@
 use clap::Parser;
-use runjs::RunJs;
+use runjs::{RunJs, RunJsConfig, RunJsRc};
 use std::path::PathBuf;
 
 /// A JavaScript/TypeScript runtime with chroot capabilities
@@ -13,6 +13,14 @@ struct Cli {
     /// Optional chroot path (defaults to current directory)
     #[arg(long, short)]
     chroot: Option<PathBuf>,
+
+    /// Run the tests registered with `runjs.test` instead of just the module
+    #[arg(long)]
+    test: bool,
+
+    /// Host `runjs.fetch` may reach; repeat for more. Fetches are denied otherwise.
+    #[arg(long = "allow-host", value_name = "HOST")]
+    allow_hosts: Vec<String>,
 }
 
 
@@ -20,10 +28,50 @@ struct Cli {
 async fn main() {
     let cli = Cli::parse();
 
-    let runjs = RunJs::new(runjs::RunJsConfig {
-        chroot_path: cli.chroot.or_else(|| Some(PathBuf::from("."))),
-    });
+    // Project defaults from the closest .runjsrc.json, overridden by CLI flags
+    let mut config = RunJsConfig::default();
+    match RunJsRc::discover(&cli.file) {
+        Ok(Some((path, rc))) => match path.parent() {
+            Some(base_dir) => rc.apply(base_dir, &mut config),
+            None => eprintln!("warning: ignoring {}: no parent directory", path.display()),
+        },
+        Ok(None) => {}
+        Err(e) => eprintln!("warning: ignoring {}: {}", RunJsRc::FILE_NAME, e),
+    }
+    if let Some(chroot) = cli.chroot {
+        config.chroot_path = Some(chroot);
+    }
+    if config.chroot_path.is_none() {
+        config.chroot_path = Some(PathBuf::from("."));
+    }
+    if !cli.allow_hosts.is_empty() {
+        config.allowed_hosts = Some(cli.allow_hosts);
+    }
 
-    
+    let mut runjs = RunJs::new(config);
+    let file = cli.file.to_str().unwrap();
 
+    if cli.test {
+        match runjs.run_tests(file).await {
+            Ok(results) => {
+                for test in &results.tests {
+                    match &test.error {
+                        None => println!("ok   {}", test.name),
+                        Some(error) => println!("FAIL {}: {}", test.name, error),
+                    }
+                }
+                println!("\n{} passed, {} failed", results.passed(), results.failed());
+                if results.failed() > 0 {
+                    std::process::exit(1);
+                }
+            }
+            Err(e) => {
+                eprintln!("error: {}", e);
+                std::process::exit(1);
+            }
+        }
+    } else if let Err(e) = runjs.run_file(file).await {
+        eprintln!("error: {}", e);
+        std::process::exit(1);
+    }
 }
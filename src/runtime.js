@@ -4,30 +4,679 @@ function argsToMessage(...args) {
   return args.map((arg) => JSON.stringify(arg)).join(" ");
 }
 
+function write(message, isErr) {
+  // The host may capture output instead of printing it
+  if (!core.ops.op_console_capture(message, isErr)) {
+    core.print(`${message}\n`, isErr);
+  }
+}
+
 globalThis.console = {
   log: (...args) => {
-    core.print(`${argsToMessage(...args)}\n`, false);
+    write(argsToMessage(...args), false);
   },
   error: (...args) => {
-    core.print(`${argsToMessage(...args)}\n`, true);
+    write(argsToMessage(...args), true);
   },
 };
 
+const exitHandlers = [];
+const tests = [];
+const idleCallbacks = [];
+// Signal names mapped to their listener id and callbacks
+const signalListeners = new Map();
+
+// Wait for signals on listener `id` in the background without keeping the
+// event loop alive, dispatching to `callbacks` until the listener is removed.
+async function listenForSignal(id, name, callbacks) {
+  while (true) {
+    const received = core.ops.op_signal_recv(id);
+    core.unrefOpPromise(received);
+    if (!(await received)) {
+      return;
+    }
+    for (const callback of [...callbacks]) {
+      callback(name);
+    }
+  }
+}
+
 globalThis.runjs = {
-  readFile: async (path) => {
-    return await core.ops.op_read_file(path);
+  version: Object.freeze(core.ops.op_version_info()),
+  readFile: async (path, options) => {
+    return await core.ops.op_read_file(path, options);
   },
-  writeFile: async (path, contents) => {
-    return await core.ops.op_write_file(path, contents);
+  readParsed: async (path, format) => {
+    return await core.ops.op_read_parsed(path, format);
+  },
+  writeFile: async (path, contents, options) => {
+    return await core.ops.op_write_file(path, contents, options);
+  },
+  // Read or write `path` relative to a module, given its `import.meta.url`
+  readFileRelative: async (moduleUrl, path, options) => {
+    const resolved = core.ops.op_resolve_relative(String(moduleUrl), String(path));
+    return await core.ops.op_read_file(resolved, options);
+  },
+  writeFileRelative: async (moduleUrl, path, contents, options) => {
+    const resolved = core.ops.op_resolve_relative(String(moduleUrl), String(path));
+    return await core.ops.op_write_file(resolved, contents, options);
+  },
+  // Write nested objects of file contents under `base`, creating directories
+  writeTree: async (base, tree) => {
+    return await core.ops.op_write_tree(String(base), tree);
   },
   removeFile: (path) => {
     return core.ops.op_remove_file(path);
   },
+  incrementCounter: async (path, by = 1) => {
+    return await core.ops.op_counter_incr(path, by);
+  },
+  // Compress `src` into `dst` without holding either in memory, resolving
+  // with the size of `dst`
+  gzipFile: async (src, dst, { level } = {}) => {
+    return await core.ops.op_gzip_file(src, dst, level ?? null);
+  },
+  gunzipFile: async (src, dst) => {
+    return await core.ops.op_gunzip_file(src, dst);
+  },
+  // Requires the host to enable heap snapshots
+  writeHeapSnapshot: (path) => {
+    core.ops.op_write_heap_snapshot(String(path));
+  },
+  dirHash: async (path, algo = "sha256") => {
+    return await core.ops.op_dir_hash(path, algo);
+  },
+  displayPath: (path) => {
+    return core.ops.op_display_path(path);
+  },
+  detectMime: async (path) => {
+    return await core.ops.op_detect_mime(path);
+  },
+  tail: (path, { fromEnd = false } = {}) => {
+    const id = core.ops.op_tail_open(path, fromEnd);
+    return {
+      [Symbol.asyncIterator]() {
+        return this;
+      },
+      async next() {
+        const line = await core.ops.op_tail_next(id);
+        return line === null
+          ? { value: undefined, done: true }
+          : { value: line, done: false };
+      },
+      async return() {
+        core.ops.op_tail_close(id);
+        return { value: undefined, done: true };
+      },
+    };
+  },
   fetch: async (url) => {
     return await core.ops.op_fetch(url);
   },
+  selfSource: () => {
+    return core.ops.op_self_source();
+  },
+  onExit: (callback) => {
+    exitHandlers.push(callback);
+  },
+  // Call `callback` once the event loop has nothing left to do. With
+  // `once: false` it fires again each time later work drains. Returns a
+  // function that unregisters it.
+  onIdle: (callback, { once = true } = {}) => {
+    const entry = { callback, once };
+    idleCallbacks.push(entry);
+    return () => {
+      const index = idleCallbacks.indexOf(entry);
+      if (index !== -1) {
+        idleCallbacks.splice(index, 1);
+      }
+    };
+  },
+  test: (name, fn) => {
+    tests.push({ name, fn });
+  },
+  emit: (value) => {
+    core.ops.op_emit(value);
+  },
+  progress: (fraction, message = "") => {
+    core.ops.op_progress(Number(fraction), String(message));
+  },
+  parseQuery: (query) => {
+    return core.ops.op_parse_query(String(query));
+  },
+  buildQuery: (params) => {
+    // Format numbers and booleans the way JavaScript prints them
+    const toText = (value) =>
+      typeof value === "number" || typeof value === "boolean" ? String(value) : value ?? null;
+    const pairs = Object.entries(params ?? {}).map(([key, value]) => [
+      key,
+      Array.isArray(value) ? value.map(toText) : toText(value),
+    ]);
+    return core.ops.op_build_query(pairs);
+  },
+  render: (template, vars = {}) => {
+    return core.ops.op_render_template(String(template), vars);
+  },
+  diff: (oldText, newText, { format = "unified" } = {}) => {
+    return core.ops.op_text_diff(String(oldText), String(newText), format);
+  },
+  // Resolves with how long a 0 ms timer scheduled by this call took to fire,
+  // in milliseconds. Start it before synchronous work to measure that work.
+  eventLoopLag: () => {
+    const scheduled = Date.now();
+    return new Promise((resolve) => {
+      setTimeout(() => resolve(Date.now() - scheduled), 0);
+    });
+  },
+  // Call `callback(name)` each time the process receives SIGINT or SIGTERM.
+  // Returns a function that unregisters the callback.
+  onSignal: (name, callback) => {
+    if (name !== "SIGINT" && name !== "SIGTERM") {
+      throw new TypeError(`Unsupported signal: ${name}`);
+    }
+    let listener = signalListeners.get(name);
+    if (!listener) {
+      listener = { id: core.ops.op_signal_listen(name), callbacks: new Set() };
+      signalListeners.set(name, listener);
+      listenForSignal(listener.id, name, listener.callbacks);
+    }
+    listener.callbacks.add(callback);
+    return () => {
+      listener.callbacks.delete(callback);
+      if (listener.callbacks.size === 0 && signalListeners.get(name) === listener) {
+        signalListeners.delete(name);
+        core.ops.op_signal_unlisten(listener.id);
+      }
+    };
+  },
+  // Handshake with an https URL and describe the server's leaf certificate
+  tlsInfo: async (url) => {
+    const info = await core.ops.op_tls_peer_cert(String(url));
+    return {
+      ...info,
+      notBefore: new Date(info.notBefore),
+      notAfter: new Date(info.notAfter),
+    };
+  },
+  // Number of setTimeout/setInterval timers that have neither fired nor
+  // been cleared
+  activeTimers: () => core.ops.op_timer_count(),
+  // Cancel every pending setTimeout/setInterval so the event loop can drain,
+  // returning how many were cleared
+  clearAllTimers: () => core.ops.op_timer_clear_all(),
+  // Running count, min, max, mean and variance of the numbers pushed,
+  // without keeping them in memory
+  stats: () => {
+    const id = core.ops.op_stats_new();
+    return {
+      push: (...values) => {
+        for (const value of values) {
+          core.ops.op_stats_push(id, Number(value));
+        }
+      },
+      result: () => core.ops.op_stats_result(id),
+    };
+  },
+  // Compile and instantiate wasm bytes, resolving to the instance's exports
+  instantiateWasm: async (bytes, imports = {}) => {
+    const { instance } = await WebAssembly.instantiate(bytes, imports);
+    return instance.exports;
+  },
+  jwt: {
+    sign: (claims, key, { alg = "HS256", header = {} } = {}) => {
+      return core.ops.op_jwt_sign(header, claims, key, alg);
+    },
+    verify: (token, key, { alg = "HS256" } = {}) => {
+      return core.ops.op_jwt_verify(token, key, alg);
+    },
+  },
+  // HMAC-SHA256 signed strings, such as session cookies. verifyValue returns
+  // null if the token was tampered with or signed with another key.
+  signValue: (value, key) => core.ops.op_sign_value(String(value), String(key)),
+  verifyValue: (signed, key) => core.ops.op_verify_value(String(signed), String(key)),
+  msgpack: {
+    encode: (value) => core.ops.op_msgpack_encode(value),
+    decode: (bytes) => core.ops.op_msgpack_decode(bytes),
+  },
+  semver: {
+    parse: (version) => core.ops.op_semver_parse(version),
+    satisfies: (version, range) => core.ops.op_semver_satisfies(version, range),
+    compare: (a, b) => core.ops.op_semver_compare(a, b),
+  },
+  // Cooperative stop: sets a flag long-running work can poll
+  requestStop: (reason) => {
+    core.ops.op_request_stop(reason === undefined ? null : String(reason));
+  },
+  stopRequested: () => {
+    return core.ops.op_stop_requested();
+  },
+  // Settle like `promise`, or reject with a TimeoutError after `ms`. The
+  // timer is cancelled either way so it never holds the event loop open.
+  withTimeout: (promise, ms) => {
+    const [id, fired] = startTimer(ms);
+    const timeout = fired.then((fired) => {
+      // A cleared timer never settles, so it can't win the race
+      if (!fired) return new Promise(() => {});
+      const error = new Error(`Timed out after ${ms}ms`);
+      error.name = "TimeoutError";
+      throw error;
+    });
+    return Promise.race([promise, timeout]).finally(() => {
+      core.ops.op_timer_clear(id);
+    });
+  },
+};
+
+// Called by the host once the event loop has drained
+globalThis[Symbol.for("runjs.runExitHandlers")] = () => {
+  while (exitHandlers.length > 0) {
+    const handler = exitHandlers.pop();
+    try {
+      const result = handler();
+      if (result instanceof Promise) {
+        result.catch((error) => console.error(`onExit handler failed: ${error}`));
+      }
+    } catch (error) {
+      console.error(`onExit handler failed: ${error}`);
+    }
+  }
+};
+
+// Called by the host each time the event loop drains. Re-arming callbacks
+// only fire if work ran since the last call. Returns whether any fired.
+globalThis[Symbol.for("runjs.runIdleCallbacks")] = (afterWork) => {
+  const due = idleCallbacks.filter((entry) => entry.once || afterWork);
+  for (const entry of due) {
+    if (entry.once) {
+      idleCallbacks.splice(idleCallbacks.indexOf(entry), 1);
+    }
+  }
+  for (const { callback } of due) {
+    callback();
+  }
+  return due.length > 0;
+};
+
+// Called by the host after the test module has been evaluated
+globalThis[Symbol.for("runjs.runTests")] = async () => {
+  const results = [];
+  for (const { name, fn } of tests) {
+    try {
+      await fn();
+      results.push({ name, error: null });
+    } catch (error) {
+      results.push({ name, error: String(error) });
+    }
+  }
+  return results;
+};
+
+// Last-resort error hooks. A handler returning true suppresses the error,
+// if the host allows it.
+function dispatchError(message, source, lineno, colno, error) {
+  const handler = globalThis.onerror;
+  if (typeof handler !== "function") {
+    return false;
+  }
+  const handled = handler(message, source, lineno, colno, error);
+  return handled === true && core.ops.op_error_suppression_allowed();
+}
+
+core.setReportExceptionCallback((error) => {
+  if (!dispatchError(String(error), undefined, undefined, undefined, error)) {
+    core.reportUnhandledException(error);
+  }
+});
+
+core.setUnhandledPromiseRejectionHandler((promise, reason) => {
+  const handler = globalThis.onunhandledrejection;
+  if (typeof handler !== "function") {
+    return false;
+  }
+  const handled = handler({ promise, reason });
+  return handled === true && core.ops.op_error_suppression_allowed();
+});
+
+// Called by the host when a module's top-level code throws
+globalThis[Symbol.for("runjs.reportError")] = (details) => {
+  const error = new Error(details.message ?? details.exceptionMessage);
+  if (details.name) {
+    error.name = details.name;
+  }
+  if (details.stack) {
+    error.stack = details.stack;
+  }
+  const frame = details.frames[0] ?? {};
+  return dispatchError(
+    details.exceptionMessage,
+    frame.fileName,
+    frame.lineNumber,
+    frame.columnNumber,
+    error,
+  );
+};
+
+function startTimer(delay, repeat = false) {
+  const id = core.ops.op_timer_start(Number(delay) || 0, repeat);
+  return [id, core.ops.op_timer_wait(id)];
+}
+
+// `await setTimeout(ms)` sleeps; `setTimeout(callback, ms, ...args)` schedules
+// the callback and returns an id for clearTimeout
+globalThis.setTimeout = (callback, delay, ...args) => {
+  if (typeof callback !== "function") {
+    const [, fired] = startTimer(callback);
+    // A cleared sleep never settles, so code after it doesn't run
+    return fired.then((fired) => fired ? undefined : new Promise(() => {}));
+  }
+  const [id, fired] = startTimer(delay);
+  fired.then((fired) => {
+    if (fired) callback(...args);
+  });
+  return id;
 };
 
-globalThis.setTimeout = async (delay) => {
-  await core.ops.op_set_timeout(delay);
-};
\ No newline at end of file
+// The interval holds one timer slot from here until it is cleared
+globalThis.setInterval = (callback, delay, ...args) => {
+  const [id, fired] = startTimer(delay, true);
+  const tick = (fired) => {
+    if (!fired) return;
+    core.ops.op_timer_wait(id).then(tick);
+    callback(...args);
+  };
+  fired.then(tick);
+  return id;
+};
+
+globalThis.clearTimeout = (id) => {
+  if (typeof id === "number") core.ops.op_timer_clear(id);
+};
+
+globalThis.clearInterval = globalThis.clearTimeout;
+
+// Request-scoped storage that follows async continuations, built on V8's
+// continuation-preserved embedder data
+class AsyncLocalStorage {
+  #variable = new core.AsyncVariable();
+
+  run(store, callback, ...args) {
+    const previous = this.#variable.enter(store);
+    try {
+      return callback(...args);
+    } finally {
+      core.setAsyncContext(previous);
+    }
+  }
+
+  getStore() {
+    return this.#variable.get();
+  }
+}
+
+globalThis.AsyncLocalStorage = AsyncLocalStorage;
+
+// Host-configured locale and time zone, so dates and locale-sensitive
+// formatting do not depend on the machine running the script. V8 only reads
+// these from process-wide ICU defaults, which runs with different settings
+// would fight over, so every Date and Intl entry point is wrapped instead.
+{
+  const { locale, timeZone } = core.ops.op_intl_defaults();
+
+  const withLocale = (locales) => locales ?? locale ?? undefined;
+  const withDefaults = (locales, options) => [
+    withLocale(locales),
+    timeZone === null || options?.timeZone !== undefined
+      ? options
+      : { ...options, timeZone },
+  ];
+  const define = (target, name, value) => {
+    Object.defineProperty(target, name, { value, writable: true, configurable: true });
+  };
+  const wrapMethod = (target, name, defaults) => {
+    const original = target[name];
+    define(target, name, function (locales, options) {
+      return original.call(this, ...defaults(locales, options));
+    });
+  };
+  const wrapIntl = (name, defaults) => {
+    const Original = Intl[name];
+    if (Original === undefined) {
+      return;
+    }
+    const Wrapped = function (locales, options) {
+      return new Original(...defaults(locales, options));
+    };
+    Object.defineProperty(Wrapped, "name", { value: name });
+    Object.setPrototypeOf(Wrapped, Original);
+    Wrapped.prototype = Original.prototype;
+    Intl[name] = Wrapped;
+  };
+
+  if (timeZone !== null) {
+    const OriginalDate = Date;
+    const proto = OriginalDate.prototype;
+    const getTime = (date) => proto.getTime.call(date);
+    const setTime = (date, time) => proto.setTime.call(date, time);
+    const hostTimezoneOffset = proto.getTimezoneOffset;
+    const wallClock = new Intl.DateTimeFormat("en-US", {
+      timeZone,
+      hourCycle: "h23",
+      era: "short",
+      year: "numeric",
+      month: "numeric",
+      day: "numeric",
+      hour: "numeric",
+      minute: "numeric",
+      second: "numeric",
+    });
+    const zoneNames = new Intl.DateTimeFormat("en-US", { timeZone, timeZoneName: "long" });
+
+    // Minutes the zone is ahead of UTC at `time`
+    const zoneOffset = (time) => {
+      // Whole seconds within the range formatToParts accepts
+      const seconds = Math.max(-8.64e15, Math.min(8.64e15, time - (((time % 1000) + 1000) % 1000)));
+      const parts = {};
+      for (const { type, value } of wallClock.formatToParts(seconds)) {
+        parts[type] = value;
+      }
+      const year = parts.era === "BC" ? 1 - Number(parts.year) : Number(parts.year);
+      const fields = new OriginalDate(0);
+      fields.setUTCFullYear(year, parts.month - 1, parts.day);
+      fields.setUTCHours(parts.hour, parts.minute, parts.second);
+      return (getTime(fields) - seconds) / 60000;
+    };
+    // A copy of `date` whose UTC fields read as the zone's wall clock
+    const local = (date) => {
+      const time = getTime(date);
+      return new OriginalDate(time + zoneOffset(time) * 60000);
+    };
+    // The instant whose wall clock in the zone reads as the UTC fields of `wall`
+    const fromWallClock = (wall) => {
+      if (!Number.isFinite(wall)) {
+        return NaN;
+      }
+      const guess = wall - zoneOffset(wall) * 60000;
+      return wall - zoneOffset(guess) * 60000;
+    };
+
+    // Strings with no offset are local time, except ISO dates without a time
+    const dateOnly = /^[+-]?\d{4,6}(?:-\d\d(?:-\d\d)?)?$/;
+    const explicitZone =
+      /\d\d:\d\d(?::\d\d(?:\.\d+)?)?\s*(?:Z|[+-]\d\d(?::?\d\d)?)|\b(?:GMT|UTC|UT|[ECMP][SD]T)\b/i;
+    const parse = (string) => {
+      string = String(string);
+      const host = OriginalDate.parse(string);
+      if (Number.isNaN(host) || dateOnly.test(string.trim()) || explicitZone.test(string)) {
+        return host;
+      }
+      // The host read the string in its own zone; reread its wall clock in ours
+      const hostOffset = hostTimezoneOffset.call(new OriginalDate(host));
+      return fromWallClock(host - hostOffset * 60000);
+    };
+
+    const ZonedDate = function (...args) {
+      if (new.target === undefined) {
+        return proto.toString.call(new OriginalDate());
+      }
+      let value;
+      if (args.length === 0) {
+        value = OriginalDate.now();
+      } else if (args.length === 1) {
+        value = typeof args[0] === "string" ? parse(args[0]) : args[0];
+      } else {
+        value = fromWallClock(OriginalDate.UTC(...args));
+      }
+      return Reflect.construct(OriginalDate, [value], new.target);
+    };
+    Object.defineProperty(ZonedDate, "name", { value: "Date" });
+    Object.defineProperty(ZonedDate, "length", { value: 7 });
+    Object.setPrototypeOf(ZonedDate, OriginalDate);
+    ZonedDate.prototype = proto;
+    define(ZonedDate, "parse", parse);
+    define(proto, "constructor", ZonedDate);
+    globalThis.Date = ZonedDate;
+
+    define(proto, "getTimezoneOffset", function () {
+      const time = getTime(this);
+      return Number.isNaN(time) ? NaN : -zoneOffset(time);
+    });
+    const fields = ["FullYear", "Month", "Date", "Hours", "Minutes", "Seconds", "Milliseconds"];
+    for (const field of [...fields, "Day"]) {
+      const getUTC = proto[`getUTC${field}`];
+      define(proto, `get${field}`, function () {
+        return getUTC.call(local(this));
+      });
+    }
+    define(proto, "getYear", function () {
+      return this.getFullYear() - 1900;
+    });
+    for (const field of fields) {
+      const setUTC = proto[`setUTC${field}`];
+      define(proto, `set${field}`, function (...args) {
+        const time = getTime(this);
+        // Like the builtin, setFullYear on an invalid date starts from local midnight 1970
+        const wall = Number.isNaN(time)
+          ? new OriginalDate(field === "FullYear" ? 0 : NaN)
+          : local(this);
+        setUTC.apply(wall, args);
+        return setTime(this, fromWallClock(getTime(wall)));
+      });
+    }
+
+    const days = "Sun Mon Tue Wed Thu Fri Sat".split(" ");
+    const months = "Jan Feb Mar Apr May Jun Jul Aug Sep Oct Nov Dec".split(" ");
+    const pad = (value, width = 2) => String(value).padStart(width, "0");
+    const dateString = (wall) => {
+      const year = wall.getUTCFullYear();
+      return `${days[wall.getUTCDay()]} ${months[wall.getUTCMonth()]} ${pad(wall.getUTCDate())} ` +
+        (year < 0 ? `-${pad(-year, 6)}` : pad(year, 4));
+    };
+    const timeString = (time, wall) => {
+      const offset = zoneOffset(time);
+      const sign = offset < 0 ? "-" : "+";
+      // Historical offsets can include seconds, which the builtin drops
+      const minutes = Math.floor(Math.abs(offset));
+      const zone = `GMT${sign}${pad(Math.floor(minutes / 60))}${pad(minutes % 60)}`;
+      const name = zoneNames.formatToParts(time).find((part) => part.type === "timeZoneName");
+      const clock = [wall.getUTCHours(), wall.getUTCMinutes(), wall.getUTCSeconds()];
+      return `${clock.map((n) => pad(n)).join(":")} ${zone} (${name.value})`;
+    };
+    define(proto, "toString", function () {
+      const time = getTime(this);
+      if (Number.isNaN(time)) {
+        return "Invalid Date";
+      }
+      const wall = local(this);
+      return `${dateString(wall)} ${timeString(time, wall)}`;
+    });
+    define(proto, "toDateString", function () {
+      return Number.isNaN(getTime(this)) ? "Invalid Date" : dateString(local(this));
+    });
+    define(proto, "toTimeString", function () {
+      const time = getTime(this);
+      return Number.isNaN(time) ? "Invalid Date" : timeString(time, local(this));
+    });
+  }
+
+  if (locale !== null || timeZone !== null) {
+    wrapIntl("DateTimeFormat", withDefaults);
+    for (const method of ["toLocaleString", "toLocaleDateString", "toLocaleTimeString"]) {
+      wrapMethod(Date.prototype, method, withDefaults);
+    }
+  }
+
+  if (locale !== null) {
+    const localeOnly = (locales, options) => [withLocale(locales), options];
+    for (const name of [
+      "Collator",
+      "DisplayNames",
+      "ListFormat",
+      "NumberFormat",
+      "PluralRules",
+      "RelativeTimeFormat",
+      "Segmenter",
+    ]) {
+      wrapIntl(name, localeOnly);
+    }
+    wrapMethod(Number.prototype, "toLocaleString", localeOnly);
+    wrapMethod(BigInt.prototype, "toLocaleString", localeOnly);
+    for (const method of ["toLocaleUpperCase", "toLocaleLowerCase"]) {
+      const original = String.prototype[method];
+      define(String.prototype, method, function (locales) {
+        return original.call(this, withLocale(locales));
+      });
+    }
+    const localeCompare = String.prototype.localeCompare;
+    define(String.prototype, "localeCompare", function (that, locales, options) {
+      return localeCompare.call(this, that, withLocale(locales), options);
+    });
+  }
+}
+
+// Minimal Deno namespace for scripts written against Deno, when enabled
+if (core.ops.op_deno_compat()) {
+  Object.assign(Deno, {
+    readTextFile: (path) => globalThis.runjs.readFile(String(path)),
+    writeTextFile: (path, data) => globalThis.runjs.writeFile(String(path), String(data)),
+    readDir: async function* (path) {
+      yield* await core.ops.op_read_dir(String(path));
+    },
+    env: {
+      get: (key) => core.ops.op_get_env(key) ?? undefined,
+      set: (key, value) => core.ops.op_set_env(key, String(value)),
+      has: (key) => core.ops.op_get_env(key) != null,
+      toObject: () => Object.fromEntries(
+        core.ops.op_env_keys().map((key) => [key, core.ops.op_get_env(key)]),
+      ),
+    },
+    // Scripts are not given command-line arguments
+    args: Object.freeze([]),
+  });
+}
+
+globalThis.process = {
+  env: new Proxy({}, {
+    get: (_target, key) => {
+      if (typeof key !== "string") return undefined;
+      return core.ops.op_get_env(key) ?? undefined;
+    },
+    set: (_target, key, value) => {
+      core.ops.op_set_env(String(key), String(value));
+      return true;
+    },
+    deleteProperty: (_target, key) => {
+      core.ops.op_delete_env(String(key));
+      return true;
+    },
+    has: (_target, key) => {
+      return typeof key === "string" && core.ops.op_get_env(key) != null;
+    },
+    ownKeys: () => core.ops.op_env_keys(),
+    getOwnPropertyDescriptor: (_target, key) => {
+      if (typeof key !== "string") return undefined;
+      const value = core.ops.op_get_env(key);
+      if (value == null) return undefined;
+      return { value, writable: true, enumerable: true, configurable: true };
+    },
+  }),
+};